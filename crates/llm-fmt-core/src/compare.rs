@@ -0,0 +1,206 @@
+//! Structural diff between two parsed [`Value`] trees.
+//!
+//! Produces a compact delta keyed by path (`added`/`removed`/`changed`)
+//! instead of the two full documents, so an LLM can be shown what changed
+//! between two configs or API responses without reconciling them itself.
+
+use indexmap::IndexMap;
+
+use crate::value::Value;
+
+/// Options controlling how array elements are matched up during a diff.
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Field used to align array elements of objects (e.g. `"id"`), so
+    /// reordering and insertions don't diff every subsequent element.
+    /// Falls back to positional alignment when absent or not found on an element.
+    pub array_key: Option<String>,
+}
+
+type Bucket = IndexMap<String, Value>;
+
+/// Computes a delta `Value` with `added`, `removed`, and `changed` sections
+/// keyed by path, e.g. `users[3].email: {from, to}`.
+pub fn diff(left: &Value, right: &Value, options: &DiffOptions) -> Value {
+    let mut added = Bucket::new();
+    let mut removed = Bucket::new();
+    let mut changed = Bucket::new();
+    walk("", left, right, options, &mut added, &mut removed, &mut changed);
+
+    let mut result = IndexMap::new();
+    result.insert("added".to_string(), Value::Object(added));
+    result.insert("removed".to_string(), Value::Object(removed));
+    result.insert("changed".to_string(), Value::Object(changed));
+    Value::Object(result)
+}
+
+fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+fn walk(
+    path: &str,
+    left: &Value,
+    right: &Value,
+    options: &DiffOptions,
+    added: &mut Bucket,
+    removed: &mut Bucket,
+    changed: &mut Bucket,
+) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            for (key, rv) in r {
+                let child_path = join_path(path, key);
+                match l.get(key) {
+                    Some(lv) => walk(&child_path, lv, rv, options, added, removed, changed),
+                    None => {
+                        added.insert(child_path, rv.clone());
+                    }
+                }
+            }
+            for (key, lv) in l {
+                if !r.contains_key(key) {
+                    removed.insert(join_path(path, key), lv.clone());
+                }
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => diff_arrays(path, l, r, options, added, removed, changed),
+        (l, r) if l != r => {
+            let mut from_to = IndexMap::new();
+            from_to.insert("from".to_string(), l.clone());
+            from_to.insert("to".to_string(), r.clone());
+            changed.insert(path.to_string(), Value::Object(from_to));
+        }
+        _ => {}
+    }
+}
+
+fn diff_arrays(
+    path: &str,
+    left: &[Value],
+    right: &[Value],
+    options: &DiffOptions,
+    added: &mut Bucket,
+    removed: &mut Bucket,
+    changed: &mut Bucket,
+) {
+    let Some(key_field) = &options.array_key else {
+        for (index, rv) in right.iter().enumerate() {
+            let child_path = format!("{path}[{index}]");
+            match left.get(index) {
+                Some(lv) => walk(&child_path, lv, rv, options, added, removed, changed),
+                None => {
+                    added.insert(child_path, rv.clone());
+                }
+            }
+        }
+        for (index, lv) in left.iter().enumerate().skip(right.len()) {
+            removed.insert(format!("{path}[{index}]"), lv.clone());
+        }
+        return;
+    };
+
+    // Partition each side into elements that carry the key field (aligned
+    // by key) and elements that don't (falling back to positional
+    // alignment among themselves), so neither side silently drops data.
+    fn partition<'a>(items: &'a [Value], key_field: &str) -> (IndexMap<String, &'a Value>, Vec<&'a Value>) {
+        let mut keyed = IndexMap::new();
+        let mut unkeyed = Vec::new();
+        for item in items {
+            match item.get_path(key_field) {
+                Some(k) => {
+                    keyed.insert(scalar_key(k), item);
+                }
+                None => unkeyed.push(item),
+            }
+        }
+        (keyed, unkeyed)
+    }
+
+    let (left_by_key, left_unkeyed) = partition(left, key_field);
+    let (right_by_key, right_unkeyed) = partition(right, key_field);
+
+    for (key, rv) in &right_by_key {
+        let child_path = format!("{path}[{key}]");
+        match left_by_key.get(key) {
+            Some(lv) => walk(&child_path, lv, rv, options, added, removed, changed),
+            None => {
+                added.insert(child_path, (*rv).clone());
+            }
+        }
+    }
+    for (key, lv) in &left_by_key {
+        if !right_by_key.contains_key(key) {
+            removed.insert(format!("{path}[{key}]"), (*lv).clone());
+        }
+    }
+
+    for (index, rv) in right_unkeyed.iter().enumerate() {
+        let child_path = format!("{path}[{index}]");
+        match left_unkeyed.get(index) {
+            Some(lv) => walk(&child_path, lv, rv, options, added, removed, changed),
+            None => {
+                added.insert(child_path, (*rv).clone());
+            }
+        }
+    }
+    for (index, lv) in left_unkeyed.iter().enumerate().skip(right_unkeyed.len()) {
+        removed.insert(format!("{path}[{index}]"), (*lv).clone());
+    }
+}
+
+fn scalar_key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Array(_) | Value::Object(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obj(pairs: &[(&str, Value)]) -> Value {
+        Value::Object(pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect())
+    }
+
+    #[test]
+    fn diff_arrays_by_key_detects_added_removed_and_changed() {
+        let left = Value::Array(vec![
+            obj(&[("id", Value::Number(1.0)), ("name", Value::String("a".into()))]),
+            obj(&[("id", Value::Number(2.0)), ("name", Value::String("b".into()))]),
+        ]);
+        let right = Value::Array(vec![
+            obj(&[("id", Value::Number(2.0)), ("name", Value::String("c".into()))]),
+            obj(&[("id", Value::Number(3.0)), ("name", Value::String("d".into()))]),
+        ]);
+        let options = DiffOptions { array_key: Some("id".to_string()) };
+        let delta = diff(&left, &right, &options);
+        let changed = delta.get_path("changed").and_then(Value::as_object).unwrap();
+        assert!(changed.contains_key("[2].name"));
+        let added = delta.get_path("added").and_then(Value::as_object).unwrap();
+        assert!(added.contains_key("[3]"));
+        let removed = delta.get_path("removed").and_then(Value::as_object).unwrap();
+        assert!(removed.contains_key("[1]"));
+    }
+
+    #[test]
+    fn diff_arrays_falls_back_to_positional_for_elements_missing_the_key() {
+        // Neither element carries the `id` field, so a keyed diff must not
+        // silently drop them; they should still show up via positional
+        // alignment instead of vanishing.
+        let left = Value::Array(vec![obj(&[("name", Value::String("a".into()))])]);
+        let right = Value::Array(vec![obj(&[("name", Value::String("b".into()))])]);
+        let options = DiffOptions { array_key: Some("id".to_string()) };
+        let delta = diff(&left, &right, &options);
+        let changed = delta.get_path("changed").and_then(Value::as_object).unwrap();
+        assert!(changed.contains_key("[0].name"), "unkeyed element should still be diffed positionally");
+    }
+}
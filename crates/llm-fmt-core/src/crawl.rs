@@ -0,0 +1,226 @@
+//! Directory-crawling input source.
+//!
+//! Walks a directory tree respecting `.gitignore`/`.ignore` files (via the
+//! `ignore` crate's `WalkBuilder`), parses each recognized file with the
+//! matching [`crate::parsers`] parser, and merges the results into a single
+//! [`Value`] so a whole config or data directory can be collapsed into one
+//! token-efficient blob.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+
+use crate::error::{Error, Result};
+use crate::parsers::parser_for_extension;
+use crate::value::Value;
+
+/// How matched files are combined into the final [`Value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrawlShape {
+    /// A `Value::Object` keyed by each file's path relative to the root.
+    Object,
+    /// A `Value::Array` of `{path, data}` records.
+    Array,
+}
+
+/// Configuration for a directory crawl.
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    pub shape: CrawlShape,
+    /// Include files with no recognized parser, read as raw text.
+    pub include_unrecognized: bool,
+    /// Stop after the first file of each recognized extension.
+    pub one_per_extension: bool,
+    pub max_files: Option<usize>,
+    pub max_bytes: Option<u64>,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self {
+            shape: CrawlShape::Object,
+            include_unrecognized: false,
+            one_per_extension: false,
+            max_files: None,
+            max_bytes: None,
+        }
+    }
+}
+
+/// A directory tree to be crawled into a single [`Value`].
+pub struct CrawlSource {
+    root: PathBuf,
+    config: CrawlConfig,
+}
+
+impl CrawlSource {
+    pub fn new(root: impl Into<PathBuf>, config: CrawlConfig) -> Self {
+        Self {
+            root: root.into(),
+            config,
+        }
+    }
+
+    /// Walk the tree and merge every matched file into one [`Value`].
+    pub fn read(&self) -> Result<Value> {
+        let mut object = indexmap::IndexMap::new();
+        let mut array = Vec::new();
+        let mut seen_extensions = HashSet::new();
+        let mut files_read = 0usize;
+        let mut bytes_read = 0u64;
+
+        for entry in WalkBuilder::new(&self.root).build() {
+            let entry = entry.map_err(|e| Error::Parse(e.to_string()))?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            if let Some(max_files) = self.config.max_files {
+                if files_read >= max_files {
+                    break;
+                }
+            }
+
+            let path = entry.path();
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let parser = parser_for_extension(extension);
+            if parser.is_none() && !self.config.include_unrecognized {
+                continue;
+            }
+            if self.config.one_per_extension && !seen_extensions.insert(extension.to_string()) {
+                continue;
+            }
+
+            let bytes = std::fs::read(path)?;
+            if let Some(max_bytes) = self.config.max_bytes {
+                if bytes_read + bytes.len() as u64 > max_bytes {
+                    break;
+                }
+            }
+            bytes_read += bytes.len() as u64;
+            files_read += 1;
+
+            let value = match &parser {
+                Some(parser) => parser.parse(&bytes)?,
+                None => Value::String(String::from_utf8_lossy(&bytes).into_owned()),
+            };
+
+            let rel_path = relative_path(&self.root, path);
+            match self.config.shape {
+                CrawlShape::Object => {
+                    object.insert(rel_path, value);
+                }
+                CrawlShape::Array => {
+                    let mut record = indexmap::IndexMap::new();
+                    record.insert("path".to_string(), Value::String(rel_path));
+                    record.insert("data".to_string(), value);
+                    array.push(Value::Object(record));
+                }
+            }
+        }
+
+        Ok(match self.config.shape {
+            CrawlShape::Object => Value::Object(object),
+            CrawlShape::Array => Value::Array(array),
+        })
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A self-cleaning temp directory, since the crate has no `tempfile` dependency.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("llm-fmt-core-crawl-test-{name}"));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn crawl_object_shape_keys_by_relative_path() {
+        let dir = TempDir::new("object-shape");
+        std::fs::write(dir.path().join("a.json"), r#"{"n": 1}"#).unwrap();
+        std::fs::write(dir.path().join("b.json"), r#"{"n": 2}"#).unwrap();
+
+        let source = CrawlSource::new(dir.path(), CrawlConfig::default());
+        let value = source.read().unwrap();
+
+        let object = value.as_object().unwrap();
+        assert_eq!(object.len(), 2);
+        assert!(object.contains_key("a.json"));
+        assert!(object.contains_key("b.json"));
+    }
+
+    #[test]
+    fn crawl_array_shape_produces_path_and_data_records() {
+        let dir = TempDir::new("array-shape");
+        std::fs::write(dir.path().join("a.json"), r#"{"n": 1}"#).unwrap();
+
+        let config = CrawlConfig {
+            shape: CrawlShape::Array,
+            ..CrawlConfig::default()
+        };
+        let source = CrawlSource::new(dir.path(), config);
+        let value = source.read().unwrap();
+
+        let records = value.as_array().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = records[0].as_object().unwrap();
+        assert_eq!(record.get("path").unwrap().as_str(), Some("a.json"));
+        assert!(record.get("data").is_some());
+    }
+
+    #[test]
+    fn crawl_skips_unrecognized_extensions_unless_included() {
+        let dir = TempDir::new("unrecognized");
+        std::fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+
+        let default_result = CrawlSource::new(dir.path(), CrawlConfig::default()).read().unwrap();
+        assert_eq!(default_result.as_object().unwrap().len(), 0);
+
+        let config = CrawlConfig {
+            include_unrecognized: true,
+            ..CrawlConfig::default()
+        };
+        let included_result = CrawlSource::new(dir.path(), config).read().unwrap();
+        let object = included_result.as_object().unwrap();
+        assert_eq!(object.get("notes.txt").unwrap().as_str(), Some("hello"));
+    }
+
+    #[test]
+    fn crawl_respects_max_files() {
+        let dir = TempDir::new("max-files");
+        std::fs::write(dir.path().join("a.json"), r#"{"n": 1}"#).unwrap();
+        std::fs::write(dir.path().join("b.json"), r#"{"n": 2}"#).unwrap();
+
+        let config = CrawlConfig {
+            max_files: Some(1),
+            ..CrawlConfig::default()
+        };
+        let value = CrawlSource::new(dir.path(), config).read().unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 1);
+    }
+}
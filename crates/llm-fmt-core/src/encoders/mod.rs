@@ -0,0 +1,375 @@
+//! Encoders that render a [`Value`] tree into an output format.
+
+pub mod template;
+
+pub use template::TemplateEncoder;
+
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+/// Renders a [`Value`] tree into a `String`.
+pub trait Encoder {
+    fn encode(&self, value: &Value) -> Result<String>;
+
+    /// Text written once before the first streamed record; see
+    /// [`crate::pipeline::Pipeline::run_stream`].
+    fn stream_prefix(&self) -> &str {
+        ""
+    }
+
+    /// Text written once after the last streamed record.
+    fn stream_suffix(&self) -> &str {
+        ""
+    }
+
+    /// Text written between consecutive streamed records.
+    fn stream_separator(&self) -> &str {
+        "\n"
+    }
+
+    /// Encode a single streamed record. `index` is the record's position
+    /// (0-based) in the stream, which array-framed encoders (JSON, TOON)
+    /// need to decide whether they're opening a fresh list element.
+    ///
+    /// The default renders the record as a standalone value via [`Encoder::encode`];
+    /// encoders with list framing override this to nest under that framing instead.
+    fn encode_record(&self, value: &Value, index: usize) -> Result<String> {
+        let _ = index;
+        self.encode(value)
+    }
+}
+
+/// The compact, token-efficient TOON format this crate exists to produce.
+pub struct ToonEncoder {
+    pub sort_keys: bool,
+}
+
+impl Encoder for ToonEncoder {
+    fn encode(&self, value: &Value) -> Result<String> {
+        let mut out = String::new();
+        write_toon(value, 0, self.sort_keys, &mut out);
+        Ok(out)
+    }
+
+    fn encode_record(&self, value: &Value, _index: usize) -> Result<String> {
+        // Each record is a top-level list element, one `- ` per line.
+        let mut out = "- ".to_string();
+        match value {
+            Value::Object(_) | Value::Array(_) => {
+                out.push('\n');
+                write_toon(value, 1, self.sort_keys, &mut out);
+            }
+            scalar => out.push_str(&scalar_to_string(scalar)),
+        }
+        Ok(out)
+    }
+}
+
+fn write_toon(value: &Value, indent: usize, sort_keys: bool, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            if sort_keys {
+                entries.sort_by_key(|(k, _)| k.as_str());
+            }
+            for (key, val) in entries {
+                out.push_str(&"  ".repeat(indent));
+                out.push_str(key);
+                out.push(':');
+                match val {
+                    Value::Object(_) | Value::Array(_) => {
+                        out.push('\n');
+                        write_toon(val, indent + 1, sort_keys, out);
+                    }
+                    scalar => {
+                        out.push(' ');
+                        out.push_str(&scalar_to_string(scalar));
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                out.push_str(&"  ".repeat(indent));
+                out.push_str("- ");
+                match item {
+                    Value::Object(_) | Value::Array(_) => {
+                        out.push('\n');
+                        write_toon(item, indent + 1, sort_keys, out);
+                    }
+                    scalar => out.push_str(&format!("{}\n", scalar_to_string(scalar))),
+                }
+            }
+        }
+        scalar => out.push_str(&scalar_to_string(scalar)),
+    }
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Plain JSON output, optionally with sorted keys.
+pub struct JsonEncoder {
+    pub sort_keys: bool,
+}
+
+impl Encoder for JsonEncoder {
+    fn encode(&self, value: &Value) -> Result<String> {
+        let json = to_json(value, self.sort_keys);
+        serde_json::to_string_pretty(&json).map_err(|e| Error::Encode(e.to_string()))
+    }
+
+    fn stream_prefix(&self) -> &str {
+        "[\n"
+    }
+
+    fn stream_suffix(&self) -> &str {
+        "\n]\n"
+    }
+
+    fn stream_separator(&self) -> &str {
+        ",\n"
+    }
+
+    fn encode_record(&self, value: &Value, _index: usize) -> Result<String> {
+        let json = to_json(value, self.sort_keys);
+        serde_json::to_string(&json).map_err(|e| Error::Encode(e.to_string()))
+    }
+}
+
+fn to_json(value: &Value, sort_keys: bool) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => serde_json::Value::Bool(*b),
+        Value::Number(n) => serde_json::Number::from_f64(*n)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::String(s) => serde_json::Value::String(s.clone()),
+        Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|v| to_json(v, sort_keys)).collect())
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            if sort_keys {
+                entries.sort_by_key(|(k, _)| k.as_str());
+            }
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), to_json(v, sort_keys)))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// YAML output, optionally with sorted keys.
+pub struct YamlEncoder {
+    pub sort_keys: bool,
+}
+
+impl Encoder for YamlEncoder {
+    fn encode(&self, value: &Value) -> Result<String> {
+        let json = to_json(value, self.sort_keys);
+        serde_yaml::to_string(&json).map_err(|e| Error::Encode(e.to_string()))
+    }
+}
+
+/// Delimiter-separated output (CSV/TSV) for arrays of flat objects.
+pub struct CsvEncoder {
+    pub delimiter: u8,
+    /// Header row locked in by the first record streamed through
+    /// [`Encoder::encode_record`]; unused by the buffered [`Encoder::encode`] path.
+    stream_headers: std::cell::RefCell<Option<Vec<String>>>,
+}
+
+impl CsvEncoder {
+    pub fn new(delimiter: u8) -> Self {
+        Self {
+            delimiter,
+            stream_headers: std::cell::RefCell::new(None),
+        }
+    }
+
+    fn write_row(&self, headers: &[String], row: &Value) -> Result<String> {
+        let map = row.as_object();
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(Vec::new());
+        let record: Vec<String> = headers
+            .iter()
+            .map(|h| {
+                map.and_then(|m| m.get(h))
+                    .map(scalar_to_string)
+                    .unwrap_or_default()
+            })
+            .collect();
+        writer
+            .write_record(&record)
+            .map_err(|e| Error::Encode(e.to_string()))?;
+        let bytes = writer.into_inner().map_err(|e| Error::Encode(e.to_string()))?;
+        Ok(String::from_utf8(bytes)
+            .map_err(|e| Error::Encode(e.to_string()))?
+            .trim_end_matches(['\r', '\n'])
+            .to_string())
+    }
+}
+
+impl Encoder for CsvEncoder {
+    fn encode(&self, value: &Value) -> Result<String> {
+        let rows = value
+            .as_array()
+            .ok_or_else(|| Error::Encode("CSV/TSV output requires an array of records".into()))?;
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(Vec::new());
+        let mut headers: Vec<String> = Vec::new();
+        for row in rows {
+            if let Some(map) = row.as_object() {
+                for key in map.keys() {
+                    if !headers.contains(key) {
+                        headers.push(key.clone());
+                    }
+                }
+            }
+        }
+        writer
+            .write_record(&headers)
+            .map_err(|e| Error::Encode(e.to_string()))?;
+        for row in rows {
+            let map = row.as_object();
+            let record: Vec<String> = headers
+                .iter()
+                .map(|h| {
+                    map.and_then(|m| m.get(h))
+                        .map(scalar_to_string)
+                        .unwrap_or_default()
+                })
+                .collect();
+            writer
+                .write_record(&record)
+                .map_err(|e| Error::Encode(e.to_string()))?;
+        }
+        let bytes = writer.into_inner().map_err(|e| Error::Encode(e.to_string()))?;
+        String::from_utf8(bytes).map_err(|e| Error::Encode(e.to_string()))
+    }
+
+    /// CSV/TSV streams can't discover the full header set up front, so the
+    /// first streamed record's keys become the header row for the rest of
+    /// the stream.
+    fn encode_record(&self, value: &Value, index: usize) -> Result<String> {
+        let headers = {
+            let mut cell = self.stream_headers.borrow_mut();
+            if cell.is_none() {
+                let keys = value
+                    .as_object()
+                    .map(|map| map.keys().cloned().collect())
+                    .unwrap_or_default();
+                *cell = Some(keys);
+            }
+            cell.clone().unwrap_or_default()
+        };
+
+        let row = self.write_row(&headers, value)?;
+        Ok(if index == 0 {
+            format!("{}\n{row}", headers.join(&(self.delimiter as char).to_string()))
+        } else {
+            row
+        })
+    }
+}
+
+/// Builds the [`Encoder`] registered under a format name, used by
+/// `PipelineBuilder::with_format`.
+pub fn encoder_for_format(format: &str, sort_keys: bool) -> Result<Box<dyn Encoder>> {
+    match format.to_lowercase().as_str() {
+        "toon" => Ok(Box::new(ToonEncoder { sort_keys })),
+        "json" => Ok(Box::new(JsonEncoder { sort_keys })),
+        "yaml" | "yml" => Ok(Box::new(YamlEncoder { sort_keys })),
+        "csv" => Ok(Box::new(CsvEncoder::new(b','))),
+        "tsv" => Ok(Box::new(CsvEncoder::new(b'\t'))),
+        other => Err(Error::UnsupportedFormat(other.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn user(name: &str, age: i64) -> Value {
+        let mut map = IndexMap::new();
+        map.insert("name".to_string(), Value::String(name.to_string()));
+        map.insert("age".to_string(), Value::Number(age as f64));
+        Value::Object(map)
+    }
+
+    #[test]
+    fn encoder_for_format_is_case_insensitive_and_rejects_unknown() {
+        assert!(encoder_for_format("TOON", false).is_ok());
+        assert!(encoder_for_format("Json", false).is_ok());
+        assert!(matches!(
+            encoder_for_format("xml", false),
+            Err(Error::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn toon_encoder_writes_nested_indented_lines() {
+        let encoder = ToonEncoder { sort_keys: true };
+        let value = Value::Array(vec![user("Alice", 30)]);
+        let out = encoder.encode(&value).unwrap();
+        assert_eq!(out, "- \n  age: 30\n  name: Alice\n");
+    }
+
+    #[test]
+    fn toon_encoder_record_nests_under_a_list_marker() {
+        let encoder = ToonEncoder { sort_keys: true };
+        let record = encoder.encode_record(&user("Bob", 40), 0).unwrap();
+        assert_eq!(record, "- \n  age: 40\n  name: Bob\n");
+    }
+
+    #[test]
+    fn json_encoder_sorts_keys_when_requested() {
+        let encoder = JsonEncoder { sort_keys: true };
+        let out = encoder.encode(&user("Carol", 25)).unwrap();
+        let age_pos = out.find("age").unwrap();
+        let name_pos = out.find("name").unwrap();
+        assert!(age_pos < name_pos);
+    }
+
+    #[test]
+    fn json_encoder_stream_framing_wraps_records_in_an_array() {
+        let encoder = JsonEncoder { sort_keys: false };
+        assert_eq!(encoder.stream_prefix(), "[\n");
+        assert_eq!(encoder.stream_suffix(), "\n]\n");
+        assert_eq!(encoder.stream_separator(), ",\n");
+        let record = encoder.encode_record(&user("Dee", 50), 0).unwrap();
+        assert_eq!(record, r#"{"age":50.0,"name":"Dee"}"#);
+    }
+
+    #[test]
+    fn csv_encoder_buffered_union_of_keys_becomes_the_header() {
+        let encoder = CsvEncoder::new(b',');
+        let rows = Value::Array(vec![user("Eve", 22)]);
+        let out = encoder.encode(&rows).unwrap();
+        assert_eq!(out, "name,age\nEve,22\n");
+    }
+
+    #[test]
+    fn csv_encoder_streaming_locks_headers_from_the_first_record() {
+        let encoder = CsvEncoder::new(b',');
+        let first = encoder.encode_record(&user("Frank", 33), 0).unwrap();
+        assert_eq!(first, "name,age\nFrank,33");
+        let second = encoder.encode_record(&user("Gina", 44), 1).unwrap();
+        assert_eq!(second, "Gina,44");
+    }
+}
@@ -0,0 +1,335 @@
+//! A small Jinja-style template engine for rendering a [`Value`] into
+//! bespoke text layouts (markdown tables, prompt scaffolds, ...) instead of
+//! a fixed encoder.
+//!
+//! Supports `{{ path.to.field }}` interpolation, `{% for item in items %}`
+//! iteration, `{% if cond %}` truthiness checks, and a handful of filters
+//! (`upper`, `lower`, `default(x)`, `join(sep)`).
+
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+use super::Encoder;
+
+/// Renders a [`Value`] through a template string.
+pub struct TemplateEncoder {
+    nodes: Vec<Node>,
+}
+
+impl TemplateEncoder {
+    pub fn new(template: &str) -> Result<Self> {
+        let tokens = tokenize(template);
+        let (nodes, rest) = parse_nodes(&tokens, None)?;
+        if !rest.is_empty() {
+            return Err(Error::Encode("unexpected `{% end... %}` in template".into()));
+        }
+        Ok(Self { nodes })
+    }
+}
+
+impl Encoder for TemplateEncoder {
+    fn encode(&self, value: &Value) -> Result<String> {
+        let mut out = String::new();
+        render(&self.nodes, value, &[], &mut out)?;
+        Ok(out)
+    }
+}
+
+// --- Tokenizing -------------------------------------------------------
+
+enum Token {
+    Literal(String),
+    Interp(String),
+    Tag(String),
+}
+
+fn tokenize(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    loop {
+        let next_interp = rest.find("{{");
+        let next_tag = rest.find("{%");
+        let next = match (next_interp, next_tag) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (Some(a), None) => Some(a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        };
+        let Some(start) = next else {
+            if !rest.is_empty() {
+                tokens.push(Token::Literal(rest.to_string()));
+            }
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Literal(rest[..start].to_string()));
+        }
+        let is_interp = rest[start..].starts_with("{{");
+        let close = if is_interp { "}}" } else { "%}" };
+        let body_start = start + 2;
+        let Some(close_offset) = rest[body_start..].find(close) else {
+            tokens.push(Token::Literal(rest[start..].to_string()));
+            break;
+        };
+        let body = rest[body_start..body_start + close_offset].trim().to_string();
+        tokens.push(if is_interp { Token::Interp(body) } else { Token::Tag(body) });
+        rest = &rest[body_start + close_offset + 2..];
+    }
+    tokens
+}
+
+// --- Parsing ------------------------------------------------------------
+
+enum Node {
+    Literal(String),
+    Interp(Expr),
+    For { var: String, iterable: Expr, body: Vec<Node> },
+    If { cond: Expr, body: Vec<Node> },
+}
+
+/// An interpolation expression: a path plus an optional chain of filters.
+struct Expr {
+    path: String,
+    filters: Vec<(String, Option<String>)>,
+}
+
+fn parse_expr(src: &str) -> Expr {
+    let mut parts = src.split('|');
+    let path = parts.next().unwrap_or_default().trim().to_string();
+    let filters = parts
+        .map(|f| {
+            let f = f.trim();
+            if let Some(open) = f.find('(') {
+                let name = f[..open].trim().to_string();
+                let arg = f[open + 1..]
+                    .trim_end_matches(')')
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string();
+                (name, Some(arg))
+            } else {
+                (f.to_string(), None)
+            }
+        })
+        .collect();
+    Expr { path, filters }
+}
+
+/// Parses nodes until a matching `{% end* %}` (for nested blocks) or EOF
+/// (for the top level, where `closing` is `None`). Returns the remaining,
+/// unconsumed tokens so callers can detect unbalanced blocks.
+fn parse_nodes<'a>(tokens: &'a [Token], closing: Option<&str>) -> Result<(Vec<Node>, &'a [Token])> {
+    let mut nodes = Vec::new();
+    let mut rest = tokens;
+    loop {
+        let Some((first, tail)) = rest.split_first() else {
+            return Ok((nodes, rest));
+        };
+        match first {
+            Token::Literal(text) => {
+                nodes.push(Node::Literal(text.clone()));
+                rest = tail;
+            }
+            Token::Interp(src) => {
+                nodes.push(Node::Interp(parse_expr(src)));
+                rest = tail;
+            }
+            Token::Tag(src) => {
+                if let Some(c) = closing {
+                    if src.trim() == c {
+                        return Ok((nodes, tail));
+                    }
+                }
+                if let Some(stripped) = src.strip_prefix("for ") {
+                    let (var, iterable) = stripped
+                        .split_once(" in ")
+                        .ok_or_else(|| Error::Encode(format!("malformed for-tag: {src}")))?;
+                    let (body, after) = parse_nodes(tail, Some("endfor"))?;
+                    nodes.push(Node::For {
+                        var: var.trim().to_string(),
+                        iterable: parse_expr(iterable.trim()),
+                        body,
+                    });
+                    rest = after;
+                } else if let Some(stripped) = src.strip_prefix("if ") {
+                    let (body, after) = parse_nodes(tail, Some("endif"))?;
+                    nodes.push(Node::If {
+                        cond: parse_expr(stripped.trim()),
+                        body,
+                    });
+                    rest = after;
+                } else {
+                    return Err(Error::Encode(format!("unknown template tag: {{% {src} %}}")));
+                }
+            }
+        }
+    }
+}
+
+// --- Evaluating -----------------------------------------------------------
+
+/// A named loop variable bound by an enclosing `{% for %}`.
+type Binding<'a> = (String, &'a Value);
+
+/// Resolves a path against the current scope: the first path segment is
+/// checked against loop bindings (innermost first) before falling back to
+/// a lookup rooted at the top-level value.
+fn resolve<'a>(root: &'a Value, bindings: &[Binding<'a>], path: &str) -> Option<&'a Value> {
+    let (head, rest) = path.split_once('.').unwrap_or((path, ""));
+    let bare_name = head.split('[').next().unwrap_or(head);
+    // Any `[...]` suffix directly attached to the bound name (e.g. `item[0]`
+    // in `item[0].field`) still needs to be indexed into the bound value,
+    // not dropped — only the name itself is consumed by the binding lookup.
+    let bracket_suffix = &head[bare_name.len()..];
+    for (name, value) in bindings.iter().rev() {
+        if name == bare_name {
+            if bracket_suffix.is_empty() {
+                return if rest.is_empty() { Some(value) } else { value.get_path(rest) };
+            }
+            let sub_path = if rest.is_empty() {
+                bracket_suffix.to_string()
+            } else {
+                format!("{bracket_suffix}.{rest}")
+            };
+            return value.get_path(&sub_path);
+        }
+    }
+    root.get_path(path)
+}
+
+/// Applies a filter chain in declared order. The intermediate result stays a
+/// [`Value`] (rather than collapsing to a `String` up front) so an array
+/// filter like `join` sees whatever a preceding filter (e.g. `upper`) already
+/// did to the array's elements, instead of re-deriving from the original value.
+fn apply_filters(value: Option<&Value>, filters: &[(String, Option<String>)]) -> String {
+    let mut current = value.cloned().unwrap_or(Value::Null);
+    for (name, arg) in filters {
+        current = match name.as_str() {
+            "upper" => map_strings(current, |s| s.to_uppercase()),
+            "lower" => map_strings(current, |s| s.to_lowercase()),
+            "default" => {
+                if current.is_truthy() {
+                    current
+                } else {
+                    Value::String(arg.clone().unwrap_or_default())
+                }
+            }
+            "join" => {
+                let sep = arg.as_deref().unwrap_or("");
+                let joined = match &current {
+                    Value::Array(items) => items.iter().map(render_scalar).collect::<Vec<_>>().join(sep),
+                    other => render_scalar(other),
+                };
+                Value::String(joined)
+            }
+            _ => current,
+        };
+    }
+    render_scalar(&current)
+}
+
+/// Applies a string transform to every string leaf, recursing into arrays so
+/// element-wise filters (e.g. `upper`) compose with a later `join`.
+fn map_strings(value: Value, f: impl Fn(&str) -> String + Copy) -> Value {
+    match value {
+        Value::String(s) => Value::String(f(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| map_strings(v, f)).collect()),
+        other => other,
+    }
+}
+
+fn render_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(_) | Value::Object(_) => String::new(),
+    }
+}
+
+fn render<'a>(nodes: &[Node], root: &'a Value, bindings: &[Binding<'a>], out: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Literal(text) => out.push_str(text),
+            Node::Interp(expr) => {
+                let value = resolve(root, bindings, &expr.path);
+                out.push_str(&apply_filters(value, &expr.filters));
+            }
+            Node::For { var, iterable, body } => {
+                let Some(Value::Array(items)) = resolve(root, bindings, &iterable.path) else {
+                    continue;
+                };
+                for item in items {
+                    let mut inner = bindings.to_vec();
+                    inner.push((var.clone(), item));
+                    render(body, root, &inner, out)?;
+                }
+            }
+            Node::If { cond, body } => {
+                let truthy = resolve(root, bindings, &cond.path).is_some_and(Value::is_truthy);
+                if truthy {
+                    render(body, root, bindings, out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_compose_in_declared_order() {
+        let value = Value::Array(vec![Value::String("a".into()), Value::String("b".into())]);
+        let filters = vec![("upper".to_string(), None), ("join".to_string(), Some(",".to_string()))];
+        assert_eq!(apply_filters(Some(&value), &filters), "A,B");
+    }
+
+    #[test]
+    fn join_without_upper_still_works() {
+        let value = Value::Array(vec![Value::String("a".into()), Value::String("b".into())]);
+        let filters = vec![("join".to_string(), Some("-".to_string()))];
+        assert_eq!(apply_filters(Some(&value), &filters), "a-b");
+    }
+
+    #[test]
+    fn default_applies_to_empty_string() {
+        let filters = vec![("default".to_string(), Some("n/a".to_string()))];
+        assert_eq!(apply_filters(Some(&Value::String(String::new())), &filters), "n/a");
+    }
+
+    #[test]
+    fn resolve_indexes_into_a_bound_loop_variable() {
+        let root = Value::Null;
+        let item = Value::Array(vec![Value::String("a".into()), Value::String("b".into())]);
+        let bindings: Vec<Binding> = vec![("item".to_string(), &item)];
+
+        let resolved = resolve(&root, &bindings, "item[0]");
+        assert_eq!(resolved, Some(&Value::String("a".into())));
+    }
+
+    #[test]
+    fn resolve_indexes_then_follows_a_dotted_field_on_a_bound_loop_variable() {
+        let mut entry = indexmap::IndexMap::new();
+        entry.insert("field".to_string(), Value::String("value".into()));
+        let item = Value::Array(vec![Value::Object(entry)]);
+        let root = Value::Null;
+        let bindings: Vec<Binding> = vec![("item".to_string(), &item)];
+
+        let resolved = resolve(&root, &bindings, "item[0].field");
+        assert_eq!(resolved, Some(&Value::String("value".into())));
+    }
+
+    #[test]
+    fn for_loop_renders_indexed_access_to_the_loop_variable() {
+        let rows = vec![Value::Array(vec![Value::String("x".into()), Value::String("y".into())])];
+        let mut root_map = indexmap::IndexMap::new();
+        root_map.insert("rows".to_string(), Value::Array(rows));
+        let root = Value::Object(root_map);
+
+        let encoder = TemplateEncoder::new("{% for row in rows %}{{ row[1] }}{% endfor %}").unwrap();
+        assert_eq!(encoder.encode(&root).unwrap(), "y");
+    }
+}
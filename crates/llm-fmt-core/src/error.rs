@@ -0,0 +1,28 @@
+//! The crate's error type.
+
+use thiserror::Error as ThisError;
+
+/// Convenience alias used throughout the crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors produced while parsing, filtering, encoding, or orchestrating a conversion.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("failed to read input: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse input: {0}")]
+    Parse(String),
+
+    #[error("failed to encode output: {0}")]
+    Encode(String),
+
+    #[error("invalid filter: {0}")]
+    Filter(String),
+
+    #[error("invalid pipeline configuration: {0}")]
+    Config(String),
+
+    #[error("unsupported format: {0}")]
+    UnsupportedFormat(String),
+}
@@ -0,0 +1,117 @@
+//! Filters that transform a [`Value`] before it reaches an encoder.
+
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+/// A transformation applied to a [`Value`] between parsing and encoding.
+pub trait Filter {
+    fn apply(&self, value: Value) -> Result<Value>;
+}
+
+/// Keeps only the subtree reached by a path expression, e.g. `users[*].name`.
+pub struct IncludeFilter {
+    expr: String,
+}
+
+impl IncludeFilter {
+    pub fn new(expr: impl Into<String>) -> Result<Self> {
+        let expr = expr.into();
+        if expr.trim().is_empty() {
+            return Err(Error::Filter("include path expression must not be empty".into()));
+        }
+        Ok(Self { expr })
+    }
+}
+
+impl Filter for IncludeFilter {
+    fn apply(&self, value: Value) -> Result<Value> {
+        // Path resolution lives alongside the template encoder's dotted
+        // lookups; see `Value::get_path_all` for the shared implementation.
+        let matches = value.get_path_all(&self.expr);
+        if matches.is_empty() {
+            return Err(Error::Filter(format!("path not found: {}", self.expr)));
+        }
+        // A wildcard segment (`[*]`) collects every match into an array;
+        // a plain path resolves to at most one match.
+        if self.expr.contains("[*]") {
+            Ok(Value::Array(matches.into_iter().cloned().collect()))
+        } else {
+            Ok(matches[0].clone())
+        }
+    }
+}
+
+/// Truncates a `Value` tree to a maximum nesting depth.
+pub struct MaxDepthFilter {
+    max_depth: usize,
+}
+
+impl MaxDepthFilter {
+    pub fn new(max_depth: usize) -> Result<Self> {
+        if max_depth == 0 {
+            return Err(Error::Filter("max_depth must be at least 1".into()));
+        }
+        Ok(Self { max_depth })
+    }
+}
+
+impl Filter for MaxDepthFilter {
+    fn apply(&self, value: Value) -> Result<Value> {
+        Ok(truncate(value, self.max_depth))
+    }
+}
+
+fn truncate(value: Value, remaining: usize) -> Value {
+    if remaining == 0 {
+        return Value::Null;
+    }
+    match value {
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|v| truncate(v, remaining - 1)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, truncate(v, remaining - 1)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn nested(depth: usize) -> Value {
+        if depth == 0 {
+            Value::String("leaf".into())
+        } else {
+            let mut map = IndexMap::new();
+            map.insert("child".to_string(), nested(depth - 1));
+            Value::Object(map)
+        }
+    }
+
+    #[test]
+    fn max_depth_filter_rejects_zero() {
+        assert!(MaxDepthFilter::new(0).is_err());
+    }
+
+    #[test]
+    fn max_depth_filter_truncates_beyond_the_limit() {
+        let filter = MaxDepthFilter::new(2).unwrap();
+        let result = filter.apply(nested(4)).unwrap();
+
+        let level1 = result.as_object().unwrap().get("child").unwrap();
+        let level2 = level1.as_object().unwrap().get("child").unwrap();
+        assert_eq!(*level2, Value::Null);
+    }
+
+    #[test]
+    fn max_depth_filter_leaves_shallow_values_untouched() {
+        let filter = MaxDepthFilter::new(5).unwrap();
+        let result = filter.apply(nested(1)).unwrap();
+        assert_eq!(result, nested(1));
+    }
+}
@@ -0,0 +1,239 @@
+//! Full-text index and query filter over parsed [`Value`] documents.
+//!
+//! Feature-gated behind `index`. Flattens a parsed `Value`'s records into
+//! documents, tokenizes the configured text fields, scores each document
+//! against a query with BM25, and keeps the top-N — so a caller can pull
+//! "the 20 most relevant users matching 'payment failed'" out of a document
+//! far larger than the prompt budget before it ever reaches an encoder.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::filters::Filter;
+use crate::value::Value;
+
+const DEFAULT_TOP_K: usize = 10;
+const DEFAULT_K1: f64 = 1.2;
+const DEFAULT_B: f64 = 0.75;
+
+/// A filter that ranks records by BM25 relevance to a query and keeps only
+/// the top-scoring ones.
+pub struct SearchFilter {
+    query: String,
+    fields: Vec<String>,
+    top_k: usize,
+    min_score: f64,
+    k1: f64,
+    b: f64,
+}
+
+impl SearchFilter {
+    pub fn new(query: impl Into<String>, fields: Vec<String>) -> Self {
+        Self {
+            query: query.into(),
+            fields,
+            top_k: DEFAULT_TOP_K,
+            min_score: 0.0,
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+        }
+    }
+
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    pub fn with_min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score;
+        self
+    }
+}
+
+impl Filter for SearchFilter {
+    fn apply(&self, value: Value) -> Result<Value> {
+        if self.query.trim().is_empty() {
+            return Err(Error::Filter("search query must not be empty".into()));
+        }
+
+        let documents = flatten_documents(value);
+        let query_terms = tokenize(&self.query);
+        let corpus: Vec<Vec<String>> = documents
+            .iter()
+            .map(|doc| tokenize(&searchable_text(doc, &self.fields)))
+            .collect();
+
+        let avg_len = if corpus.is_empty() {
+            0.0
+        } else {
+            corpus.iter().map(|d| d.len()).sum::<usize>() as f64 / corpus.len() as f64
+        };
+        let doc_freq = document_frequency(&corpus, &query_terms);
+        let n = corpus.len() as f64;
+
+        let mut scored: Vec<(f64, Value)> = documents
+            .into_iter()
+            .zip(corpus.iter())
+            .map(|(doc, terms)| {
+                let score = bm25_score(terms, &query_terms, &doc_freq, n, avg_len, self.k1, self.b);
+                (score, doc)
+            })
+            .filter(|(score, _)| *score >= self.min_score)
+            .collect();
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(self.top_k);
+
+        Ok(Value::Array(scored.into_iter().map(|(_, doc)| doc).collect()))
+    }
+}
+
+/// Splits a parsed value into individual documents: the elements of an
+/// array, the values of an object, or the value itself as a single document.
+fn flatten_documents(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items,
+        Value::Object(map) => map.into_values().collect(),
+        other => vec![other],
+    }
+}
+
+/// The text a document is scored against: the configured dotted field
+/// paths if given, otherwise every string leaf in the document.
+fn searchable_text(doc: &Value, fields: &[String]) -> String {
+    if fields.is_empty() {
+        let mut text = String::new();
+        collect_strings(doc, &mut text);
+        text
+    } else {
+        fields
+            .iter()
+            .filter_map(|field| doc.get_path(field))
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn collect_strings(value: &Value, out: &mut String) {
+    match value {
+        Value::String(s) => {
+            out.push_str(s);
+            out.push(' ');
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn document_frequency(corpus: &[Vec<String>], query_terms: &[String]) -> HashMap<String, usize> {
+    let mut freq = HashMap::new();
+    for term in query_terms {
+        let count = corpus.iter().filter(|doc| doc.contains(term)).count();
+        freq.insert(term.clone(), count);
+    }
+    freq
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bm25_score(
+    doc: &[String],
+    query_terms: &[String],
+    doc_freq: &HashMap<String, usize>,
+    n: f64,
+    avg_len: f64,
+    k1: f64,
+    b: f64,
+) -> f64 {
+    if doc.is_empty() || n == 0.0 {
+        return 0.0;
+    }
+    let doc_len = doc.len() as f64;
+    query_terms
+        .iter()
+        .map(|term| {
+            let term_freq = doc.iter().filter(|w| *w == term).count() as f64;
+            if term_freq == 0.0 {
+                return 0.0;
+            }
+            let n_t = *doc_freq.get(term).unwrap_or(&0) as f64;
+            let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+            let numerator = term_freq * (k1 + 1.0);
+            let denominator = term_freq + k1 * (1.0 - b + b * doc_len / avg_len);
+            idf * numerator / denominator
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn doc(text: &str) -> Value {
+        let mut map = IndexMap::new();
+        map.insert("body".to_string(), Value::String(text.to_string()));
+        Value::Object(map)
+    }
+
+    fn documents() -> Value {
+        Value::Array(vec![
+            doc("the quick brown fox jumps over the lazy dog"),
+            doc("a payment failed for this customer"),
+            doc("the payment gateway timed out, payment failed again"),
+        ])
+    }
+
+    #[test]
+    fn search_filter_rejects_empty_query() {
+        let filter = SearchFilter::new("", vec!["body".to_string()]);
+        assert!(filter.apply(documents()).is_err());
+    }
+
+    #[test]
+    fn search_filter_ranks_more_relevant_documents_first() {
+        let filter = SearchFilter::new("payment failed", vec!["body".to_string()]);
+        let result = filter.apply(documents()).unwrap();
+        let ranked = result.as_array().unwrap();
+
+        let top = ranked[0].as_object().unwrap().get("body").unwrap().as_str().unwrap();
+        assert!(top.contains("payment failed again"));
+    }
+
+    #[test]
+    fn search_filter_respects_top_k() {
+        let filter = SearchFilter::new("payment", vec!["body".to_string()]).with_top_k(1);
+        let result = filter.apply(documents()).unwrap();
+        assert_eq!(result.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn search_filter_respects_min_score() {
+        let filter = SearchFilter::new("payment", vec!["body".to_string()]).with_min_score(1000.0);
+        let result = filter.apply(documents()).unwrap();
+        assert_eq!(result.as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn search_filter_with_no_fields_searches_every_string_leaf() {
+        let filter = SearchFilter::new("fox", vec![]);
+        let result = filter.apply(documents()).unwrap();
+        let ranked = result.as_array().unwrap();
+        let top = ranked[0].as_object().unwrap().get("body").unwrap().as_str().unwrap();
+        assert!(top.contains("fox"));
+    }
+
+    #[test]
+    fn tokenize_lowercases_and_splits_on_non_alphanumeric() {
+        assert_eq!(tokenize("Payment-Failed!"), vec!["payment", "failed"]);
+    }
+}
@@ -2,13 +2,20 @@
 //!
 //! This crate provides the core functionality for llm-fmt:
 //! - Parsing various input formats (JSON, YAML, XML, CSV)
+//! - Crawling whole directory trees into a single input (see [`crawl`])
 //! - Encoding to token-efficient output formats (TOON, JSON, YAML, TSV)
 //! - Filtering and transforming data structures
 //! - Pipeline orchestration
+//! - An optional BM25 search filter over parsed documents (see [`index`], `index` feature)
 
+pub mod compare;
+pub mod crawl;
 pub mod encoders;
 pub mod error;
 pub mod filters;
+#[cfg(feature = "index")]
+pub mod index;
+pub mod metrics;
 pub mod parsers;
 pub mod pipeline;
 pub mod value;
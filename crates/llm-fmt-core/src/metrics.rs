@@ -0,0 +1,149 @@
+//! Token-accounting: estimate how many prompt tokens a conversion saves.
+
+/// Estimates how many LLM tokens a string would cost.
+///
+/// Kept behind a trait so the default heuristic can later be swapped for a
+/// real BPE backend without touching callers.
+pub trait Tokenizer {
+    fn estimate(&self, text: &str) -> usize;
+}
+
+/// A fast heuristic tokenizer that approximates GPT-style BPE without
+/// depending on a real vocabulary: word-like runs are charged roughly one
+/// token per `chars_per_token` characters (subwords), punctuation runs are
+/// charged one token per character (most punctuation is its own BPE token),
+/// and whitespace is free, since it typically merges into a neighboring token.
+pub struct HeuristicTokenizer {
+    pub chars_per_token: f64,
+}
+
+impl Default for HeuristicTokenizer {
+    fn default() -> Self {
+        Self { chars_per_token: 4.0 }
+    }
+}
+
+impl Tokenizer for HeuristicTokenizer {
+    fn estimate(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        let mut tokens = 0usize;
+        for run in runs(text) {
+            tokens += match run.kind {
+                RunKind::Word => ((run.len as f64) / self.chars_per_token).ceil().max(1.0) as usize,
+                RunKind::Punctuation => run.len,
+                RunKind::Whitespace => 0,
+            };
+        }
+        tokens.max(1)
+    }
+}
+
+#[derive(PartialEq, Eq)]
+enum RunKind {
+    Word,
+    Punctuation,
+    Whitespace,
+}
+
+struct Run {
+    kind: RunKind,
+    len: usize,
+}
+
+fn run_kind(c: char) -> RunKind {
+    if c.is_whitespace() {
+        RunKind::Whitespace
+    } else if c.is_alphanumeric() {
+        RunKind::Word
+    } else {
+        RunKind::Punctuation
+    }
+}
+
+fn runs(text: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for c in text.chars() {
+        let kind = run_kind(c);
+        match runs.last_mut() {
+            Some(run) if run.kind == kind => run.len += 1,
+            _ => runs.push(Run { kind, len: 1 }),
+        }
+    }
+    runs
+}
+
+/// Token-accounting summary for a single conversion, returned by
+/// `Pipeline::run_with_report`.
+#[derive(Debug, Clone, Copy)]
+pub struct ConversionReport {
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// `output_tokens / input_tokens`; lower means more savings.
+    pub ratio: f64,
+    pub bytes_in: usize,
+    pub bytes_out: usize,
+}
+
+impl ConversionReport {
+    pub fn new(tokenizer: &dyn Tokenizer, input: &str, output: &str) -> Self {
+        let input_tokens = tokenizer.estimate(input);
+        let output_tokens = tokenizer.estimate(output);
+        let ratio = if input_tokens == 0 {
+            0.0
+        } else {
+            output_tokens as f64 / input_tokens as f64
+        };
+        Self {
+            input_tokens,
+            output_tokens,
+            ratio,
+            bytes_in: input.len(),
+            bytes_out: output.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_returns_zero_for_empty_input() {
+        let tokenizer = HeuristicTokenizer::default();
+        assert_eq!(tokenizer.estimate(""), 0);
+    }
+
+    #[test]
+    fn estimate_charges_one_token_per_char_for_punctuation() {
+        let tokenizer = HeuristicTokenizer::default();
+        assert_eq!(tokenizer.estimate("!!!"), 3);
+    }
+
+    #[test]
+    fn estimate_charges_subword_tokens_for_words_and_ignores_whitespace() {
+        let tokenizer = HeuristicTokenizer {
+            chars_per_token: 4.0,
+        };
+        // "hello" (5 chars) -> ceil(5/4) = 2 tokens; whitespace is free.
+        assert_eq!(tokenizer.estimate("hello   "), 2);
+    }
+
+    #[test]
+    fn conversion_report_computes_ratio() {
+        let tokenizer = HeuristicTokenizer::default();
+        let report = ConversionReport::new(&tokenizer, "hello world", "hi");
+        assert_eq!(report.ratio, report.output_tokens as f64 / report.input_tokens as f64);
+        assert_eq!(report.bytes_in, "hello world".len());
+        assert_eq!(report.bytes_out, "hi".len());
+    }
+
+    #[test]
+    fn conversion_report_ratio_is_zero_for_empty_input() {
+        let tokenizer = HeuristicTokenizer::default();
+        let report = ConversionReport::new(&tokenizer, "", "anything");
+        assert_eq!(report.input_tokens, 0);
+        assert_eq!(report.ratio, 0.0);
+    }
+}
@@ -0,0 +1,226 @@
+//! Orchestrates a conversion: parse, filter, encode.
+
+use std::io::{BufRead, Write};
+
+use crate::compare::{diff, DiffOptions};
+use crate::crawl::{CrawlConfig, CrawlSource};
+use crate::encoders::{encoder_for_format, Encoder, TemplateEncoder};
+use crate::error::{Error, Result};
+use crate::filters::Filter;
+use crate::metrics::{ConversionReport, HeuristicTokenizer, Tokenizer};
+use crate::parsers::{auto_parser, Parser};
+use crate::value::Value;
+
+/// Where a pipeline reads its input from.
+enum Source {
+    /// A single in-memory buffer, parsed by a fixed parser.
+    Buffer(Box<dyn Parser>),
+    /// A single in-memory buffer, parsed by whichever parser best matches it.
+    Auto {
+        extension: Option<String>,
+        sniff: Option<Vec<u8>>,
+    },
+    /// A directory tree, crawled and merged into a single `Value`.
+    Crawl(CrawlSource),
+}
+
+/// Builds a [`Pipeline`] from a parser, an output format, and a filter chain.
+#[derive(Default)]
+pub struct PipelineBuilder {
+    source: Option<Source>,
+    encoder: Option<Box<dyn Encoder>>,
+    filters: Vec<Box<dyn Filter>>,
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    diff_options: DiffOptions,
+}
+
+impl PipelineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific parser for the input, e.g. [`crate::parsers::JsonParser`].
+    pub fn with_parser(mut self, parser: impl Parser + 'static) -> Self {
+        self.source = Some(Source::Buffer(Box::new(parser)));
+        self
+    }
+
+    /// Pick a parser automatically from a file extension and/or by sniffing
+    /// the input's leading bytes.
+    pub fn with_auto_parser(mut self, extension: Option<&str>, sniff: Option<&[u8]>) -> Self {
+        self.source = Some(Source::Auto {
+            extension: extension.map(str::to_string),
+            sniff: sniff.map(<[u8]>::to_vec),
+        });
+        self
+    }
+
+    /// Select the output format, e.g. `"toon"`, `"json"`, `"yaml"`, `"csv"`, `"tsv"`.
+    ///
+    /// For `"template"`, use [`PipelineBuilder::with_template`] instead,
+    /// since rendering a template additionally requires the template string.
+    pub fn with_format(mut self, format: &str, sort_keys: bool) -> Result<Self> {
+        self.encoder = Some(encoder_for_format(format, sort_keys)?);
+        Ok(self)
+    }
+
+    /// Render output through a Jinja-style template string instead of a
+    /// fixed format; see [`crate::encoders::template`].
+    pub fn with_template(mut self, template: &str) -> Result<Self> {
+        self.encoder = Some(Box::new(TemplateEncoder::new(template)?));
+        Ok(self)
+    }
+
+    /// Ingest an entire directory tree instead of a single buffer; see
+    /// [`crate::crawl`] for how files are selected and merged.
+    pub fn with_crawl_source(mut self, root: impl Into<std::path::PathBuf>, config: CrawlConfig) -> Self {
+        self.source = Some(Source::Crawl(CrawlSource::new(root, config)));
+        self
+    }
+
+    /// Append a filter to the chain; filters run in the order added.
+    pub fn add_filter(mut self, filter: impl Filter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Use a custom [`Tokenizer`] for `Pipeline::run_with_report` instead of
+    /// the default [`HeuristicTokenizer`].
+    pub fn with_tokenizer(mut self, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.tokenizer = Some(Box::new(tokenizer));
+        self
+    }
+
+    /// Align array elements by this field (e.g. `"id"`) when `Pipeline::diff`
+    /// computes a delta, instead of the default positional alignment.
+    pub fn with_diff_array_key(mut self, key: impl Into<String>) -> Self {
+        self.diff_options.array_key = Some(key.into());
+        self
+    }
+
+    pub fn build(self) -> Result<Pipeline> {
+        let source = self
+            .source
+            .ok_or_else(|| Error::Config("pipeline requires a parser or input source".into()))?;
+        let encoder = self
+            .encoder
+            .ok_or_else(|| Error::Config("pipeline requires an output format".into()))?;
+        Ok(Pipeline {
+            source,
+            encoder,
+            filters: self.filters,
+            tokenizer: self.tokenizer.unwrap_or_else(|| Box::new(HeuristicTokenizer::default())),
+            diff_options: self.diff_options,
+        })
+    }
+}
+
+/// A fully configured parse → filter → encode conversion.
+pub struct Pipeline {
+    source: Source,
+    encoder: Box<dyn Encoder>,
+    filters: Vec<Box<dyn Filter>>,
+    tokenizer: Box<dyn Tokenizer>,
+    diff_options: DiffOptions,
+}
+
+impl Pipeline {
+    /// Run the full conversion over a single in-memory input buffer.
+    pub fn run(&self, input: &[u8]) -> Result<String> {
+        let value = self.parse(input)?;
+        let value = self.apply_filters(value)?;
+        self.encoder.encode(&value)
+    }
+
+    /// Run the full conversion and estimate how many prompt tokens it
+    /// saved, comparing the raw input against the encoded output.
+    pub fn run_with_report(&self, input: &[u8]) -> Result<(String, ConversionReport)> {
+        let output = self.run(input)?;
+        let input_text = String::from_utf8_lossy(input);
+        let report = ConversionReport::new(self.tokenizer.as_ref(), &input_text, &output);
+        Ok((output, report))
+    }
+
+    /// Parse two inputs, apply the configured filters to each, and encode
+    /// only their structural differences (see [`crate::compare`]) rather
+    /// than the full documents.
+    pub fn diff(&self, left: &[u8], right: &[u8]) -> Result<String> {
+        let left_value = self.apply_filters(self.parse(left)?)?;
+        let right_value = self.apply_filters(self.parse(right)?)?;
+        let delta = diff(&left_value, &right_value, &self.diff_options);
+        self.encoder.encode(&delta)
+    }
+
+    /// Run the full conversion for a pipeline configured with
+    /// [`PipelineBuilder::with_crawl_source`].
+    pub fn run_crawl(&self) -> Result<String> {
+        let Source::Crawl(crawl) = &self.source else {
+            return Err(Error::Config("pipeline has no crawl source configured".into()));
+        };
+        let value = crawl.read()?;
+        self.run_value(value)
+    }
+
+    /// Stream newline-delimited records (NDJSON/JSON-Lines, or
+    /// line-delimited CSV/TSV rows) through the filter chain and encoder
+    /// one record at a time, without holding the whole input in memory.
+    /// Requires a pipeline built with [`PipelineBuilder::with_parser`].
+    pub fn run_stream<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> Result<()> {
+        let Source::Buffer(parser) = &self.source else {
+            return Err(Error::Config(
+                "run_stream requires a pipeline configured with with_parser".into(),
+            ));
+        };
+
+        writer.write_all(self.encoder.stream_prefix().as_bytes())?;
+        let mut index = 0usize;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(value) = parser.parse_record(line.as_bytes())? else {
+                continue;
+            };
+            let value = self.apply_filters(value)?;
+            if index > 0 {
+                writer.write_all(self.encoder.stream_separator().as_bytes())?;
+            }
+            writer.write_all(self.encoder.encode_record(&value, index)?.as_bytes())?;
+            index += 1;
+        }
+        writer.write_all(self.encoder.stream_suffix().as_bytes())?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &[u8]) -> Result<Value> {
+        match &self.source {
+            Source::Buffer(parser) => parser.parse(input),
+            Source::Auto { extension, sniff } => {
+                let parser = auto_parser(
+                    extension.as_deref(),
+                    sniff.as_deref().or(Some(input)),
+                );
+                parser.parse(input)
+            }
+            Source::Crawl(_) => Err(Error::Config(
+                "pipeline has a crawl source configured; call run_crawl() instead".into(),
+            )),
+        }
+    }
+
+    fn apply_filters(&self, mut value: Value) -> Result<Value> {
+        for filter in &self.filters {
+            value = filter.apply(value)?;
+        }
+        Ok(value)
+    }
+
+    /// Encode an already-parsed and already-filtered value, used by callers
+    /// (such as [`crate::crawl`]) that build their own `Value` tree instead
+    /// of parsing a single buffer.
+    pub fn run_value(&self, value: Value) -> Result<String> {
+        let value = self.apply_filters(value)?;
+        self.encoder.encode(&value)
+    }
+}
@@ -0,0 +1,188 @@
+//! The dynamic value tree shared by every parser, filter, and encoder.
+
+use indexmap::IndexMap;
+
+/// A parsed, format-agnostic representation of structured data.
+///
+/// Every parser in [`crate::parsers`] produces a `Value`, every encoder in
+/// [`crate::encoders`] consumes one, and every filter in [`crate::filters`]
+/// transforms one into another. Object keys preserve insertion order so
+/// encoders can reproduce the source's field ordering by default.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(IndexMap<String, Value>),
+}
+
+impl Value {
+    /// An empty object, handy as an accumulator.
+    pub fn empty_object() -> Self {
+        Value::Object(IndexMap::new())
+    }
+
+    pub fn as_object(&self) -> Option<&IndexMap<String, Value>> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut IndexMap<String, Value>> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Whether this value is truthy under the rules used by filters and
+    /// the template encoder's `{% if %}` tag: `null`, `false`, `0`, empty
+    /// strings, and empty arrays/objects are falsy.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0,
+            Value::String(s) => !s.is_empty(),
+            Value::Array(items) => !items.is_empty(),
+            Value::Object(map) => !map.is_empty(),
+        }
+    }
+
+    /// Resolves a dotted/indexed path such as `users[0].name` against this
+    /// value, shared by [`crate::filters::IncludeFilter`] and the template
+    /// encoder's `{{ ... }}` interpolation. A `[*]` segment matches every
+    /// element of the array at that point (see [`Value::get_path_all`]); for
+    /// a wildcard path this returns the first match only.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        self.get_path_all(path).into_iter().next()
+    }
+
+    /// Resolves a dotted/indexed/wildcarded path against this value,
+    /// returning every match. A `[*]` segment expands to every element of
+    /// the array reached so far, e.g. `users[*].name` returns one entry per
+    /// user. Plain (wildcard-free) paths return at most one match.
+    pub fn get_path_all(&self, path: &str) -> Vec<&Value> {
+        let mut current = vec![self];
+        for segment in split_path(path) {
+            let mut next = Vec::new();
+            for value in current {
+                match segment {
+                    PathSegment::Key(key) => {
+                        if let Some(found) = value.as_object().and_then(|m| m.get(key)) {
+                            next.push(found);
+                        }
+                    }
+                    PathSegment::Index(index) => {
+                        if let Some(found) = value.as_array().and_then(|a| a.get(index)) {
+                            next.push(found);
+                        }
+                    }
+                    PathSegment::Wildcard => {
+                        if let Some(items) = value.as_array() {
+                            next.extend(items.iter());
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+enum PathSegment<'a> {
+    Key(&'a str),
+    Index(usize),
+    Wildcard,
+}
+
+/// Splits `users[*].name` into `[Key("users"), Wildcard, Key("name")]`.
+fn split_path(path: &str) -> Vec<PathSegment<'_>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let mut rest = part;
+        if let Some(bracket) = rest.find('[') {
+            let (key, tail) = rest.split_at(bracket);
+            if !key.is_empty() {
+                segments.push(PathSegment::Key(key));
+            }
+            rest = tail;
+            while let Some(stripped) = rest.strip_prefix('[') {
+                if let Some(end) = stripped.find(']') {
+                    let (index, tail) = stripped.split_at(end);
+                    if index == "*" {
+                        segments.push(PathSegment::Wildcard);
+                    } else if let Ok(index) = index.parse::<usize>() {
+                        segments.push(PathSegment::Index(index));
+                    }
+                    rest = &tail[1..];
+                } else {
+                    break;
+                }
+            }
+        } else if !rest.is_empty() {
+            segments.push(PathSegment::Key(rest));
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> Value {
+        let mut alice = IndexMap::new();
+        alice.insert("name".to_string(), Value::String("Alice".to_string()));
+        let mut bob = IndexMap::new();
+        bob.insert("name".to_string(), Value::String("Bob".to_string()));
+        let mut root = IndexMap::new();
+        root.insert(
+            "users".to_string(),
+            Value::Array(vec![Value::Object(alice), Value::Object(bob)]),
+        );
+        Value::Object(root)
+    }
+
+    #[test]
+    fn get_path_resolves_plain_and_indexed_segments() {
+        let root = users();
+        assert_eq!(root.get_path("users[0].name"), Some(&Value::String("Alice".to_string())));
+        assert_eq!(root.get_path("users[1].name"), Some(&Value::String("Bob".to_string())));
+        assert_eq!(root.get_path("users[2].name"), None);
+    }
+
+    #[test]
+    fn get_path_all_expands_wildcard_segments() {
+        let root = users();
+        let matches = root.get_path_all("users[*].name");
+        assert_eq!(
+            matches,
+            vec![&Value::String("Alice".to_string()), &Value::String("Bob".to_string())]
+        );
+    }
+
+    #[test]
+    fn get_path_on_wildcard_returns_only_first_match() {
+        let root = users();
+        assert_eq!(root.get_path("users[*].name"), Some(&Value::String("Alice".to_string())));
+    }
+}
@@ -0,0 +1,17 @@
+//! Writes `_native.pyi` from the function registry in `llm_fmt_py::stub`.
+//!
+//! Run as part of the Python package build so the stub never drifts from
+//! the `#[pyfunction]`s it describes: `cargo run --bin generate_stubs`.
+
+use std::path::Path;
+
+fn main() {
+    let stub = llm_fmt_py::stub::generate_stubs();
+    let out_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("python/llm_fmt/_native.pyi");
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("failed to create {}: {e}", parent.display()));
+    }
+    std::fs::write(&out_path, stub)
+        .unwrap_or_else(|e| panic!("failed to write {}: {e}", out_path.display()));
+}
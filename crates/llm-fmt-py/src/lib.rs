@@ -2,32 +2,47 @@
 //!
 //! This module exposes the Rust llm-fmt-core library to Python via `PyO3`.
 
+pub mod stub;
+
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 use llm_fmt_core::{
+    crawl::{CrawlConfig, CrawlShape},
     filters::{IncludeFilter, MaxDepthFilter},
     parsers::{CsvParser, JsonParser, XmlParser, YamlParser},
     PipelineBuilder,
 };
+#[cfg(feature = "index")]
+use llm_fmt_core::index::SearchFilter;
 
 /// Convert input data to a token-efficient format.
 ///
 /// Args:
 ///     input: Input data as bytes or string.
-///     format: Output format ("toon", "json", "yaml", "tsv", "csv"). Default: "toon".
+///     format: Output format ("toon", "json", "yaml", "tsv", "csv", "template"). Default: "toon".
 ///     `input_format`: Input format ("json", "yaml", "xml", "csv", "tsv", "auto"). Default: "auto".
 ///     `max_depth`: Maximum depth to traverse. Default: None (unlimited).
 ///     `sort_keys`: Sort object keys alphabetically. Default: False.
 ///     `include`: Path expression to extract (e.g., "users[*].name"). Default: None.
+///     template: Template string to render through when `format="template"`. Default: None.
+///     search: Query to rank records by relevance and keep only the best matches. Default: None.
+///     `search_fields`: Dotted field paths to search within; searches the whole record if omitted. Default: None.
+///     report: Return `(output, report_dict)` instead of just `output`, where `report_dict` has
+///         `input_tokens`, `output_tokens`, `ratio`, `bytes_in`, `bytes_out`. Default: False.
 ///
 /// Returns:
-///     Formatted output as string.
+///     Formatted output as string, or `(output, report_dict)` if `report=True`.
 ///
 /// Raises:
 ///     `ValueError`: If parsing or encoding fails.
 #[pyfunction]
-#[pyo3(signature = (input, /, format = "toon", input_format = "auto", max_depth = None, sort_keys = false, include = None))]
+#[pyo3(signature = (
+    input, /, format = "toon", input_format = "auto", max_depth = None, sort_keys = false,
+    include = None, template = None, search = None, search_fields = None, report = false
+))]
+#[allow(clippy::too_many_arguments)]
 fn convert(
     py: Python<'_>,
     input: &[u8],
@@ -36,8 +51,12 @@ fn convert(
     max_depth: Option<usize>,
     sort_keys: bool,
     include: Option<&str>,
-) -> PyResult<String> {
-    py.detach(|| {
+    template: Option<&str>,
+    search: Option<&str>,
+    search_fields: Option<Vec<String>>,
+    report: bool,
+) -> PyResult<Py<PyAny>> {
+    let (output, conversion_report) = py.detach(|| {
         let mut builder = PipelineBuilder::new();
 
         // Set parser based on input format
@@ -56,11 +75,222 @@ fn convert(
         }
 
         // Set encoder
+        builder = if format.eq_ignore_ascii_case("template") {
+            let template = template.ok_or_else(|| {
+                PyValueError::new_err("format=\"template\" requires a `template` string")
+            })?;
+            builder
+                .with_template(template)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?
+        } else {
+            builder
+                .with_format(format, sort_keys)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?
+        };
+
+        // Add filters
+        if let Some(depth) = max_depth {
+            let filter = MaxDepthFilter::new(depth)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            builder = builder.add_filter(filter);
+        }
+
+        if let Some(expr) = include {
+            let filter = IncludeFilter::new(expr)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            builder = builder.add_filter(filter);
+        }
+
+        if let Some(query) = search {
+            #[cfg(feature = "index")]
+            {
+                let fields = search_fields.unwrap_or_default();
+                builder = builder.add_filter(SearchFilter::new(query, fields));
+            }
+            #[cfg(not(feature = "index"))]
+            {
+                let _ = (query, search_fields);
+                return Err(PyValueError::new_err(
+                    "search requires llm-fmt-py to be built with the `index` feature",
+                ));
+            }
+        }
+
+        // Build and run
+        let pipeline = builder
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        if report {
+            let (output, conversion_report) = pipeline
+                .run_with_report(input)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            Ok((output, Some(conversion_report)))
+        } else {
+            let output = pipeline
+                .run(input)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            Ok((output, None))
+        }
+    })?;
+
+    match conversion_report {
+        Some(conversion_report) => {
+            let dict = PyDict::new(py);
+            dict.set_item("input_tokens", conversion_report.input_tokens)?;
+            dict.set_item("output_tokens", conversion_report.output_tokens)?;
+            dict.set_item("ratio", conversion_report.ratio)?;
+            dict.set_item("bytes_in", conversion_report.bytes_in)?;
+            dict.set_item("bytes_out", conversion_report.bytes_out)?;
+            Ok((output, dict).into_pyobject(py)?.unbind().into_any())
+        }
+        None => Ok(output.into_pyobject(py)?.unbind().into_any()),
+    }
+}
+
+inventory::submit! {
+    stub::FunctionStub {
+        name: "convert",
+        params: &[
+            stub::positional_param("input", "bytes", None),
+            stub::param("format", "str", Some("\"toon\"")),
+            stub::param("input_format", "str", Some("\"auto\"")),
+            stub::param("max_depth", "int | None", Some("None")),
+            stub::param("sort_keys", "bool", Some("False")),
+            stub::param("include", "str | None", Some("None")),
+            stub::param("template", "str | None", Some("None")),
+            stub::param("search", "str | None", Some("None")),
+            stub::param("search_fields", "list[str] | None", Some("None")),
+            stub::param("report", "bool", Some("False")),
+        ],
+        returns: "str | tuple[str, dict]",
+        summary: "Convert input data to a token-efficient format.",
+    }
+}
+
+/// Convert newline-delimited records (NDJSON/JSON-Lines, or line-delimited
+/// CSV/TSV rows) to a token-efficient format, one record at a time.
+///
+/// Args:
+///     input: Input data as bytes or string, one record per line.
+///     format: Output format ("toon", "json", "yaml", "tsv", "csv"). Default: "toon".
+///     `input_format`: Input format ("json", "csv", "tsv"). Default: "json".
+///     `sort_keys`: Sort object keys alphabetically. Default: False.
+///
+/// Returns:
+///     Formatted output as string.
+///
+/// Raises:
+///     `ValueError`: If parsing or encoding fails.
+#[pyfunction]
+#[pyo3(signature = (input, /, format = "toon", input_format = "json", sort_keys = false))]
+fn convert_stream(
+    py: Python<'_>,
+    input: &[u8],
+    format: &str,
+    input_format: &str,
+    sort_keys: bool,
+) -> PyResult<String> {
+    py.detach(|| {
+        let parser: Box<dyn llm_fmt_core::parsers::Parser> = match input_format.to_lowercase().as_str() {
+            "json" => Box::new(JsonParser),
+            "yaml" | "yml" => Box::new(YamlParser),
+            "csv" => Box::new(CsvParser::new()),
+            "tsv" => Box::new(CsvParser::tsv()),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported streaming input format: {input_format}"
+                )));
+            }
+        };
+
+        let pipeline = PipelineBuilder::new()
+            .with_parser(parser)
+            .with_format(format, sort_keys)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let mut output = Vec::new();
+        pipeline
+            .run_stream(input, &mut output)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        String::from_utf8(output).map_err(|e| PyValueError::new_err(e.to_string()))
+    })
+}
+
+inventory::submit! {
+    stub::FunctionStub {
+        name: "convert_stream",
+        params: &[
+            stub::positional_param("input", "bytes", None),
+            stub::param("format", "str", Some("\"toon\"")),
+            stub::param("input_format", "str", Some("\"json\"")),
+            stub::param("sort_keys", "bool", Some("False")),
+        ],
+        returns: "str",
+        summary: "Convert newline-delimited records to a token-efficient format.",
+    }
+}
+
+/// Compare two inputs and emit only their structural differences, rather
+/// than two full documents an LLM would have to reconcile itself.
+///
+/// Args:
+///     a: The "before" input, as bytes or string.
+///     b: The "after" input, as bytes or string.
+///     format: Output format for the delta ("toon", "json", "yaml", "tsv", "csv"). Default: "toon".
+///     `input_format`: Input format ("json", "yaml", "xml", "csv", "tsv", "auto"). Default: "auto".
+///     `array_key`: Field used to align array elements by identity (e.g. "id") instead of
+///         position, so reordering and insertions don't diff every element. Default: None.
+///     `sort_keys`: Sort object keys alphabetically. Default: False.
+///     `max_depth`: Maximum depth to traverse before diffing. Default: None (unlimited).
+///     `include`: Path expression to extract from both inputs before diffing. Default: None.
+///
+/// Returns:
+///     A delta with `added`, `removed`, and `changed` sections, keyed by path,
+///     formatted as `format`.
+///
+/// Raises:
+///     `ValueError`: If parsing or encoding fails.
+#[pyfunction]
+#[pyo3(signature = (
+    a, b, /, format = "toon", input_format = "auto", array_key = None, sort_keys = false,
+    max_depth = None, include = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn diff(
+    py: Python<'_>,
+    a: &[u8],
+    b: &[u8],
+    format: &str,
+    input_format: &str,
+    array_key: Option<&str>,
+    sort_keys: bool,
+    max_depth: Option<usize>,
+    include: Option<&str>,
+) -> PyResult<String> {
+    py.detach(|| {
+        let mut builder = PipelineBuilder::new();
+
+        match input_format.to_lowercase().as_str() {
+            "json" => builder = builder.with_parser(JsonParser),
+            "yaml" | "yml" => builder = builder.with_parser(YamlParser),
+            "xml" => builder = builder.with_parser(XmlParser),
+            "csv" => builder = builder.with_parser(CsvParser::new()),
+            "tsv" => builder = builder.with_parser(CsvParser::tsv()),
+            "auto" => builder = builder.with_auto_parser(None, Some(a)),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported input format: {input_format}"
+                )));
+            }
+        }
+
         builder = builder
             .with_format(format, sort_keys)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
-        // Add filters
         if let Some(depth) = max_depth {
             let filter = MaxDepthFilter::new(depth)
                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
@@ -73,17 +303,133 @@ fn convert(
             builder = builder.add_filter(filter);
         }
 
-        // Build and run
+        if let Some(key) = array_key {
+            builder = builder.with_diff_array_key(key);
+        }
+
+        let pipeline = builder
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        pipeline
+            .diff(a, b)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    })
+}
+
+inventory::submit! {
+    stub::FunctionStub {
+        name: "diff",
+        params: &[
+            stub::positional_param("a", "bytes", None),
+            stub::positional_param("b", "bytes", None),
+            stub::param("format", "str", Some("\"toon\"")),
+            stub::param("input_format", "str", Some("\"auto\"")),
+            stub::param("array_key", "str | None", Some("None")),
+            stub::param("sort_keys", "bool", Some("False")),
+            stub::param("max_depth", "int | None", Some("None")),
+            stub::param("include", "str | None", Some("None")),
+        ],
+        returns: "str",
+        summary: "Compare two inputs and emit only their structural differences.",
+    }
+}
+
+/// Convert an entire directory tree to a token-efficient format.
+///
+/// Args:
+///     root: Directory to crawl.
+///     format: Output format ("toon", "json", "yaml", "tsv", "csv"). Default: "toon".
+///     `sort_keys`: Sort object keys alphabetically. Default: False.
+///     `include`: Path expression to extract (e.g., "users[*].name"). Default: None.
+///     `max_depth`: Maximum depth to traverse. Default: None (unlimited).
+///     `as_array`: Emit a `{path, data}` record per file instead of one object keyed by path. Default: False.
+///     `include_unrecognized`: Include files with no matching parser, as raw text. Default: False.
+///     `one_per_extension`: Keep only the first file found per extension. Default: False.
+///     `max_files`: Maximum number of files to read. Default: None (unlimited).
+///     `max_bytes`: Maximum total bytes to read across all files. Default: None (unlimited).
+///
+/// Returns:
+///     Formatted output as string.
+///
+/// Raises:
+///     `ValueError`: If crawling, parsing, or encoding fails.
+#[pyfunction]
+#[pyo3(signature = (
+    root, /, format = "toon", sort_keys = false, include = None, max_depth = None,
+    as_array = false, include_unrecognized = false, one_per_extension = false,
+    max_files = None, max_bytes = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn convert_dir(
+    py: Python<'_>,
+    root: &str,
+    format: &str,
+    sort_keys: bool,
+    include: Option<&str>,
+    max_depth: Option<usize>,
+    as_array: bool,
+    include_unrecognized: bool,
+    one_per_extension: bool,
+    max_files: Option<usize>,
+    max_bytes: Option<u64>,
+) -> PyResult<String> {
+    py.detach(|| {
+        let config = CrawlConfig {
+            shape: if as_array { CrawlShape::Array } else { CrawlShape::Object },
+            include_unrecognized,
+            one_per_extension,
+            max_files,
+            max_bytes,
+        };
+
+        let mut builder = PipelineBuilder::new()
+            .with_crawl_source(root, config)
+            .with_format(format, sort_keys)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        if let Some(depth) = max_depth {
+            let filter = MaxDepthFilter::new(depth)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            builder = builder.add_filter(filter);
+        }
+
+        if let Some(expr) = include {
+            let filter = IncludeFilter::new(expr)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            builder = builder.add_filter(filter);
+        }
+
         let pipeline = builder
             .build()
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
         pipeline
-            .run(input)
+            .run_crawl()
             .map_err(|e| PyValueError::new_err(e.to_string()))
     })
 }
 
+inventory::submit! {
+    stub::FunctionStub {
+        name: "convert_dir",
+        params: &[
+            stub::positional_param("root", "str", None),
+            stub::param("format", "str", Some("\"toon\"")),
+            stub::param("sort_keys", "bool", Some("False")),
+            stub::param("include", "str | None", Some("None")),
+            stub::param("max_depth", "int | None", Some("None")),
+            stub::param("as_array", "bool", Some("False")),
+            stub::param("include_unrecognized", "bool", Some("False")),
+            stub::param("one_per_extension", "bool", Some("False")),
+            stub::param("max_files", "int | None", Some("None")),
+            stub::param("max_bytes", "int | None", Some("None")),
+        ],
+        returns: "str",
+        summary: "Convert an entire directory tree to a token-efficient format.",
+    }
+}
+
 /// Check if the Rust native module is available.
 ///
 /// Returns:
@@ -93,16 +439,37 @@ const fn is_available() -> bool {
     true
 }
 
+inventory::submit! {
+    stub::FunctionStub {
+        name: "is_available",
+        params: &[],
+        returns: "bool",
+        summary: "Check if the Rust native module is available.",
+    }
+}
+
 /// Get the version of the native module.
 #[pyfunction]
 const fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+inventory::submit! {
+    stub::FunctionStub {
+        name: "version",
+        params: &[],
+        returns: "str",
+        summary: "Get the version of the native module.",
+    }
+}
+
 /// Python module definition.
 #[pymodule]
 fn _native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(convert, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(diff, m)?)?;
     m.add_function(wrap_pyfunction!(is_available, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
     Ok(())
@@ -0,0 +1,112 @@
+//! Registry-driven generation of the `.pyi` stub for the `_native` extension module.
+//!
+//! `#[pyfunction]`s are opaque to Python tooling (mypy, IDE completion)
+//! unless a `.pyi` stub describes their signatures. Rather than maintaining
+//! a second, hand-written copy of every signature in this file, each
+//! `#[pyfunction]` in `lib.rs` submits its own [`FunctionStub`] into an
+//! [`inventory`] registry right next to its definition (see
+//! `inventory::submit!` calls in `lib.rs`). [`generate_stubs`] just renders
+//! whatever functions happen to be registered, so a new `#[pyfunction]`
+//! shows up in the stub the moment its submission is added — there's
+//! nothing else to keep in sync.
+
+/// One parameter of a stubbed function.
+pub struct ParamStub {
+    pub name: &'static str,
+    /// A Python type annotation, e.g. `"int | None"`.
+    pub ty: &'static str,
+    /// The literal default shown in the stub, e.g. `"None"` or `"\"toon\""`.
+    pub default: Option<&'static str>,
+    /// Whether this parameter sits before the `/` in the `#[pyo3(signature = ...)]`,
+    /// i.e. it can only be passed positionally at the real call site.
+    pub positional_only: bool,
+}
+
+/// One stubbed `#[pyfunction]`, submitted from beside its definition in `lib.rs`.
+pub struct FunctionStub {
+    pub name: &'static str,
+    pub params: &'static [ParamStub],
+    pub returns: &'static str,
+    /// A one-line doc comment surfaced above the stub signature.
+    pub summary: &'static str,
+}
+
+inventory::collect!(FunctionStub);
+
+/// Declares a [`ParamStub`] inline; used by the `inventory::submit!` blocks in `lib.rs`.
+pub const fn param(name: &'static str, ty: &'static str, default: Option<&'static str>) -> ParamStub {
+    ParamStub { name, ty, default, positional_only: false }
+}
+
+/// Like [`param`], but for a parameter that sits before the `/` in the
+/// `#[pyo3(signature = ...)]` and so is positional-only at the real call site.
+pub const fn positional_param(
+    name: &'static str,
+    ty: &'static str,
+    default: Option<&'static str>,
+) -> ParamStub {
+    ParamStub { name, ty, default, positional_only: true }
+}
+
+/// Renders every registered [`FunctionStub`] into the contents of `_native.pyi`.
+pub fn generate_stubs() -> String {
+    let mut functions: Vec<&FunctionStub> = inventory::iter::<FunctionStub>().collect();
+    functions.sort_by_key(|f| f.name);
+
+    let mut out = String::new();
+    out.push_str("# Generated by llm-fmt-py's stub registry (src/stub.rs). Do not edit by hand.\n\n");
+    for function in functions {
+        out.push_str(&format!("def {}(\n", function.name));
+        for (i, p) in function.params.iter().enumerate() {
+            match p.default {
+                Some(default) => out.push_str(&format!("    {}: {} = {},\n", p.name, p.ty, default)),
+                None => out.push_str(&format!("    {}: {},\n", p.name, p.ty)),
+            }
+            // Positional-only params are always a prefix (mirroring the `/` in
+            // `#[pyo3(signature = ...)]`), so the marker goes right after the
+            // last one, before the first non-positional-only param.
+            let next_is_positional_only =
+                function.params.get(i + 1).is_some_and(|next| next.positional_only);
+            if p.positional_only && !next_is_positional_only {
+                out.push_str("    /,\n");
+            }
+        }
+        out.push_str(&format!(") -> {}:\n", function.returns));
+        out.push_str(&format!("    \"\"\"{}\"\"\"\n\n", function.summary));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_stubs_includes_every_pyfunction_submitted_in_lib_rs() {
+        let stub = generate_stubs();
+        for name in ["convert", "convert_dir", "convert_stream", "diff", "is_available", "version"] {
+            assert!(stub.contains(&format!("def {name}(")), "missing stub for {name}");
+        }
+    }
+
+    #[test]
+    fn generate_stubs_marks_positional_only_params_with_a_slash() {
+        let stub = generate_stubs();
+        // `convert`'s `input` is positional-only; `format` is not.
+        let convert_start = stub.find("def convert(\n").unwrap();
+        let convert_end = stub[convert_start..].find(") ->").unwrap() + convert_start;
+        let signature = &stub[convert_start..convert_end];
+        let input_pos = signature.find("input:").unwrap();
+        let slash_pos = signature.find("/,").unwrap();
+        let format_pos = signature.find("format:").unwrap();
+        assert!(input_pos < slash_pos && slash_pos < format_pos);
+    }
+
+    #[test]
+    fn generate_stubs_omits_the_slash_when_no_params_are_positional_only() {
+        let stub = generate_stubs();
+        let start = stub.find("def is_available(\n").unwrap();
+        let end = stub[start..].find(") ->").unwrap() + start;
+        assert!(!stub[start..end].contains("/,"));
+    }
+}